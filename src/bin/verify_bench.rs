@@ -0,0 +1,183 @@
+// Verification-only benchmark. `main.rs` measures the cost of *solving* a
+// nonce; this measures the cost of *verifying* one, which is what a full
+// node actually runs on every incoming block. It replays a corpus of
+// known-answer vectors from `VECTORS_PATH` if one is checked in, solving and
+// writing a fresh one otherwise. `tests::known_answer_vectors_verify` pins
+// `is_valid_nonce` against that same corpus as a `cargo test`-visible
+// regression test, independent of whether a corpus file happens to exist.
+
+use qpow_benchmark::difficulty::Difficulty;
+use qpow_benchmark::search::solve_one;
+use qpow_benchmark::vectors::{perturb_nonce_variant, read_vectors, write_vectors, Vector};
+use qpow_math::is_valid_nonce;
+use std::path::Path;
+use std::time::Instant;
+
+/// Number of known-answer vectors solved per difficulty when there's no
+/// vectors file checked in yet.
+const VECTORS_PER_DIFFICULTY: u32 = 5;
+
+const VECTORS_PATH: &str = "verify_vectors.txt";
+
+const DIFFICULTIES: [u64; 4] = [
+    40_000_000_000,
+    46_000_000_000,
+    50_000_000_000,
+    55_000_000_000,
+];
+
+/// Max single-bit perturbations to try before concluding a nonce's validity
+/// isn't just bad luck. At the lower end of `DIFFICULTIES` the per-nonce
+/// validity probability isn't negligible, so a single fixed bit flip isn't
+/// guaranteed to invalidate a known-good nonce; trying several independent
+/// bits instead makes the odds of *all* of them also validating astronomically
+/// small without assuming any one flip works.
+const MAX_PERTURBATION_ATTEMPTS: usize = 8;
+
+/// Flips successive bits of `vector`'s nonce until one fails `is_valid_nonce`,
+/// trying up to `MAX_PERTURBATION_ATTEMPTS` bits. Panics if none of them
+/// invalidate the nonce, which at that point indicates a broken validity
+/// predicate rather than an unlucky flip.
+fn find_invalid_perturbation(vector: &Vector) -> [u8; 64] {
+    for attempt in 0..MAX_PERTURBATION_ATTEMPTS {
+        let nonce_bytes = perturb_nonce_variant(vector.nonce_bytes, attempt);
+        if !is_valid_nonce(vector.mining_hash, nonce_bytes, vector.difficulty) {
+            return nonce_bytes;
+        }
+    }
+    panic!(
+        "{} successive bit flips of a known-good nonce all still validated at difficulty {}",
+        MAX_PERTURBATION_ATTEMPTS, vector.difficulty
+    );
+}
+
+fn main() {
+    let header_hex = "e963a26e2f5712d662e5662e6ffd807b93d4a64f3c37861683dd18b922db7805";
+    let mining_hash: [u8; 32] = hex::decode(header_hex)
+        .expect("Failed to decode header hex")
+        .try_into()
+        .expect("Decoded hex is not 32 bytes");
+
+    let path = Path::new(VECTORS_PATH);
+    let vectors = if path.exists() {
+        println!("Loading known-answer vectors from {}", VECTORS_PATH);
+        read_vectors(path).expect("failed to read vectors file")
+    } else {
+        println!(
+            "No vectors file found at {}; solving {} nonces per difficulty...",
+            VECTORS_PATH, VECTORS_PER_DIFFICULTY
+        );
+        let vectors = solve_vectors(&mining_hash);
+        write_vectors(path, &vectors).expect("failed to write vectors file");
+        vectors
+    };
+
+    println!("Loaded {} known-answer vectors", vectors.len());
+    verify_known_answers(&vectors);
+}
+
+/// Solves `VECTORS_PER_DIFFICULTY` fresh nonces at each entry of
+/// `DIFFICULTIES`, to seed a new vectors file.
+fn solve_vectors(mining_hash: &[u8; 32]) -> Vec<Vector> {
+    let mut vectors = Vec::new();
+
+    for &raw_difficulty in DIFFICULTIES.iter() {
+        let difficulty = Difficulty::new(raw_difficulty)
+            .unwrap_or_else(|| panic!("difficulty table entry {} is out of range", raw_difficulty));
+
+        for _ in 0..VECTORS_PER_DIFFICULTY {
+            let nonce_bytes = solve_one(difficulty, mining_hash);
+            vectors.push(Vector {
+                mining_hash: *mining_hash,
+                nonce_bytes,
+                difficulty: difficulty.get(),
+            });
+        }
+    }
+
+    vectors
+}
+
+/// Times `is_valid_nonce` over every vector plus a deliberately-invalidated
+/// perturbation of each, asserting that the known-good vectors verify and the
+/// perturbed ones don't. A failure here means the validity predicate has
+/// drifted from the checked-in corpus.
+fn verify_known_answers(vectors: &[Vector]) {
+    let perturbed: Vec<Vector> = vectors
+        .iter()
+        .map(|vector| Vector {
+            mining_hash: vector.mining_hash,
+            nonce_bytes: find_invalid_perturbation(vector),
+            difficulty: vector.difficulty,
+        })
+        .collect();
+
+    let start_time = Instant::now();
+
+    for vector in vectors {
+        assert!(
+            is_valid_nonce(vector.mining_hash, vector.nonce_bytes, vector.difficulty),
+            "known-good vector failed to verify at difficulty {}",
+            vector.difficulty
+        );
+    }
+    for vector in &perturbed {
+        assert!(
+            !is_valid_nonce(vector.mining_hash, vector.nonce_bytes, vector.difficulty),
+            "perturbed vector unexpectedly verified at difficulty {}",
+            vector.difficulty
+        );
+    }
+
+    let elapsed_secs = start_time.elapsed().as_secs_f64();
+    let total_verifications = vectors.len() + perturbed.len();
+
+    println!(
+        "Verified {} vectors ({} known-good, {} perturbed) in {:.6} s ({:.2} verifications/s)",
+        total_verifications,
+        vectors.len(),
+        perturbed.len(),
+        elapsed_secs,
+        total_verifications as f64 / elapsed_secs
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pins `is_valid_nonce` against the checked-in vectors corpus at
+    /// `VECTORS_PATH`. Deliberately does *not* fall back to solving fresh
+    /// vectors when the corpus is absent: that would make `cargo test` mine
+    /// PoW nonces on every run (non-deterministic, and able to hit
+    /// `solve_one`'s safety-limit panic), and it pins nothing since a freshly
+    /// solved nonce trivially verifies. Skips instead, so the suite stays
+    /// fast and deterministic until a corpus is actually checked in.
+    #[test]
+    fn known_answer_vectors_verify() {
+        let path = Path::new(VECTORS_PATH);
+        if !path.exists() {
+            eprintln!(
+                "skipping known_answer_vectors_verify: no checked-in corpus at {}",
+                VECTORS_PATH
+            );
+            return;
+        }
+
+        let vectors = read_vectors(path).expect("failed to read checked-in vectors corpus");
+
+        for vector in &vectors {
+            assert!(
+                is_valid_nonce(vector.mining_hash, vector.nonce_bytes, vector.difficulty),
+                "known-good vector failed to verify at difficulty {}",
+                vector.difficulty
+            );
+            let perturbed_nonce = find_invalid_perturbation(vector);
+            assert!(
+                !is_valid_nonce(vector.mining_hash, perturbed_nonce, vector.difficulty),
+                "perturbed vector unexpectedly verified at difficulty {}",
+                vector.difficulty
+            );
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+use qpow_math::MAX_DISTANCE;
+
+/// Below this the distance target would be within a hair of `MAX_DISTANCE`,
+/// i.e. almost every nonce validates and "difficulty" stops meaning anything.
+pub const MIN_DIFFICULTY: u64 = 1;
+
+/// Above this, `MAX_DISTANCE - difficulty` underflows (or hits zero, which no
+/// nonce could ever satisfy), so it's the largest value a target can absorb.
+pub const MAX_DIFFICULTY: u64 = MAX_DISTANCE - 1;
+
+/// A validated PoW difficulty.
+///
+/// Encapsulates the mapping from the difficulty number to the distance
+/// target that `is_valid_nonce` actually checks against
+/// (`MAX_DISTANCE - difficulty`). The only way to get one is through a
+/// constructor that clamps or rejects out-of-range values, so a bad entry
+/// in a difficulty table fails loudly at construction time instead of
+/// spinning forever in the search loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Difficulty(u64);
+
+impl Difficulty {
+    /// Builds a `Difficulty`, rejecting values outside `[MIN_DIFFICULTY, MAX_DIFFICULTY]`.
+    pub fn new(value: u64) -> Option<Self> {
+        (MIN_DIFFICULTY..=MAX_DIFFICULTY).contains(&value).then_some(Self(value))
+    }
+
+    /// Clamps `value` into `[MIN_DIFFICULTY, MAX_DIFFICULTY]` instead of rejecting it.
+    pub fn saturating_new(value: u64) -> Self {
+        Self(value.clamp(MIN_DIFFICULTY, MAX_DIFFICULTY))
+    }
+
+    /// Raises the difficulty by `delta`, rejecting overflow or an out-of-range result.
+    pub fn checked_add(self, delta: u64) -> Option<Self> {
+        self.0.checked_add(delta).and_then(Self::new)
+    }
+
+    /// Lowers the difficulty by `delta`, rejecting underflow or an out-of-range result.
+    pub fn checked_sub(self, delta: u64) -> Option<Self> {
+        self.0.checked_sub(delta).and_then(Self::new)
+    }
+
+    /// The raw difficulty value.
+    pub fn get(self) -> u64 {
+        self.0
+    }
+
+    /// The distance target `is_valid_nonce` checks against.
+    pub fn target(self) -> u64 {
+        MAX_DISTANCE - self.0
+    }
+
+    /// The expected number of nonce attempts to find a valid solution.
+    ///
+    /// Each attempt succeeds with probability `target / MAX_DISTANCE`, so the
+    /// attempt count is geometrically distributed with mean
+    /// `MAX_DISTANCE / target`.
+    pub fn expected_attempts(self) -> f64 {
+        MAX_DISTANCE as f64 / self.target() as f64
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
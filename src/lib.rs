@@ -0,0 +1,4 @@
+pub mod difficulty;
+pub mod search;
+pub mod stats;
+pub mod vectors;
@@ -1,69 +1,15 @@
 // use qpow_math::get_nonce_distance;
-// use qpow_math::MAX_DISTANCE;
-use qpow_math::is_valid_nonce;
-use rand::{thread_rng, RngCore};
 use rayon::prelude::*; // Import Rayon traits
 use std::time::Instant;
 
-const NUM_SAMPLES: u32 = 50; // Number of times to find a nonce for averaging
-
-// Function to find one valid nonce and return the count
-fn find_one_nonce(difficulty: u64, mining_hash: &[u8; 32]) -> (u64, f64) {
-    let mut rng = thread_rng(); // Create RNG inside the function for thread-safety
-                                // let mut nonce_u512 = U512::zero(); // Start nonce from 0
-    let mut nonce_count: u64 = 0;
-    let mut nonce_bytes = [0u8; 64]; // Buffer for nonce bytes
-    let start_time = Instant::now();
-
-    // Loop until a valid nonce is found
-    loop {
-        nonce_count += 1;
-
-        // linear nonce
-        // let nonce_bytes = nonce_u512.to_big_endian();
-        // nonce_u512 += U512::one();
-
-        // random nonce
-        rng.fill_bytes(&mut nonce_bytes); // Generate random nonce bytes
-
-        if is_valid_nonce(*mining_hash, nonce_bytes, difficulty) {
-            //println!("Found nonce: {}", nonce_count);
-            // let nonce_distance = get_nonce_distance(*mining_hash, nonce_bytes);
-            // let nonce_difficulty = MAX_DISTANCE - nonce_distance;
-            //println!("Nonce Difficulty: {}", nonce_difficulty);
-            let elapsed_time = start_time.elapsed(); // Stop timer for this difficulty
-            let elapsed_secs = elapsed_time.as_secs_f64();
+use qpow_benchmark::difficulty::Difficulty;
+use qpow_benchmark::search::{find_one_nonce, SearchStrategy};
+use qpow_benchmark::stats::RateStats;
 
-            return (nonce_count, elapsed_secs); // Return the number of attempts
-        }
-
-        // if (nonce_count + 1) % (1000) == 0 {
-        //     println!("  Nonce count {}", nonce_count);
-        // }
-
-        // Basic safety break for extremely low difficulties or potential bugs
-        // This limit might need adjustment depending on expected counts
-        if nonce_count > difficulty.saturating_mul(100) && difficulty > 0 {
-            // e.g., allow 100x expected attempts
-            eprintln!(
-                "Warning: Exceeded safety limit ({} nonces) for difficulty {}. Skipping.",
-                nonce_count,
-                difficulty
-            );
-            return (u64::MAX, 0.0); // Indicate an issue
-        }
-        if nonce_count == u64::MAX {
-            eprintln!(
-                "Warning: Nonce count reached u64::MAX for difficulty {}. Skipping.",
-                difficulty
-            );
-            return (u64::MAX, 0.0); // Indicate an issue
-        }
-    }
-}
+const NUM_SAMPLES: u32 = 50; // Number of times to find a nonce for averaging
 
 fn main() {
-    // let mut rng = thread_rng(); // Initialize random number generator - removed as it's created per task now
+    let strategies = strategies_from_args(std::env::args().nth(1).as_deref());
 
     // Define the range of difficulties to test
     // Adjust these values based on your machine speed and desired range
@@ -86,6 +32,16 @@ fn main() {
         58_000_000_000,
     ];
 
+    // Fail loudly here rather than let a bad table entry spin forever inside
+    // `find_one_nonce`'s safety-limit loop.
+    let difficulties: Vec<Difficulty> = difficulties
+        .iter()
+        .map(|&raw| {
+            Difficulty::new(raw)
+                .unwrap_or_else(|| panic!("difficulty table entry {} is out of range", raw))
+        })
+        .collect();
+
     // Use the real header hash provided
     let header_hex = "e963a26e2f5712d662e5662e6ffd807b93d4a64f3c37861683dd18b922db7805";
     // let header_hex =    "0000000000000000000000000000000000000000000000000000000000000000";
@@ -98,60 +54,131 @@ fn main() {
     println!("Mining hash: {:?}", header_hex);
 
     for difficulty in difficulties.iter().cloned() {
-        // Clone difficulty for use
-        if difficulty == 0 {
-            continue;
-        } // Skip difficulty 0
-        let start_time = Instant::now();
+        for &strategy in &strategies {
+            run_samples(strategy, difficulty, &mining_hash);
+        }
+    }
+    println!("Measurement complete.");
+}
 
-        println!(
-            "Measuring difficulty: {} ({} samples)...",
-            difficulty,
-            NUM_SAMPLES
-        );
-        let mut total_nonce_count: u128 = 0;
-        let mut successful_samples = 0;
+/// Parses the optional CLI strategy selector (`random` | `partitioned` |
+/// `both`, default `both`) into the set of strategies to benchmark. Running
+/// `both` is how we measure the partitioned strategy head-to-head against
+/// the existing random-fill one across the difficulty table.
+fn strategies_from_args(arg: Option<&str>) -> Vec<SearchStrategy> {
+    let worker_count = rayon::current_num_threads() as u64;
+    match arg {
+        Some("random") => vec![SearchStrategy::Random],
+        Some("partitioned") => vec![SearchStrategy::Partitioned { worker_count }],
+        Some("both") | None => vec![
+            SearchStrategy::Random,
+            SearchStrategy::Partitioned { worker_count },
+        ],
+        Some(other) => {
+            eprintln!(
+                "Unknown strategy '{}' (expected random|partitioned|both), defaulting to both.",
+                other
+            );
+            vec![
+                SearchStrategy::Random,
+                SearchStrategy::Partitioned { worker_count },
+            ]
+        }
+    }
+}
+
+/// Runs `NUM_SAMPLES` independent nonce searches under `strategy` at
+/// `difficulty` and reports the aggregate and per-solve rate statistics.
+fn run_samples(strategy: SearchStrategy, difficulty: Difficulty, mining_hash: &[u8; 32]) {
+    let start_time = Instant::now();
 
-        // Use Rayon to run samples in parallel
-        let counts: Vec<(u64, f64)> = (0..NUM_SAMPLES)
+    println!(
+        "Measuring difficulty: {} strategy: {} ({} samples)...",
+        difficulty,
+        strategy.label(),
+        NUM_SAMPLES
+    );
+
+    let mut total_nonce_count: u128 = 0;
+    let mut total_busy_secs: f64 = 0.0;
+    let mut successful_samples = 0;
+    let mut per_solve_rates: Vec<f64> = Vec::new();
+
+    // The random strategy is single-threaded per sample, so samples
+    // themselves parallelize nicely across Rayon's pool. The partitioned
+    // strategy already fans out across all of its workers inside a single
+    // call, so running samples in parallel here too would oversubscribe the
+    // thread pool and blend multiple samples' worth of worker time together;
+    // samples run sequentially instead, one fully-parallel search at a time.
+    let counts: Vec<(u64, f64)> = match strategy {
+        SearchStrategy::Random => (0..NUM_SAMPLES)
             .into_par_iter()
-            .map(|_| find_one_nonce(difficulty, &mining_hash)) // Call function for each sample index (no rng passed)
-            .collect();
-
-        let mut total_elapsed_secs: f64 = 0.0;
-        // Process the results sequentially
-        for count in counts {
-            if count.0 != u64::MAX {
-                // Check if safety break was hit
-                total_nonce_count += count.0 as u128;
-                total_elapsed_secs += count.1;
-                successful_samples += 1;
-            } else {
-                eprintln!("  Skipping failed sample for difficulty {}", difficulty);
+            .map(|_| find_one_nonce(strategy, difficulty, mining_hash))
+            .collect(),
+        SearchStrategy::Partitioned { .. } => (0..NUM_SAMPLES)
+            .map(|_| find_one_nonce(strategy, difficulty, mining_hash))
+            .collect(),
+    };
+
+    // Process the results sequentially
+    for (nonce_count, elapsed_secs) in counts {
+        if nonce_count != u64::MAX {
+            // Check if safety break was hit
+            total_nonce_count += nonce_count as u128;
+            total_busy_secs += elapsed_secs;
+            successful_samples += 1;
+            if elapsed_secs > 0.0 {
+                per_solve_rates.push(nonce_count as f64 / elapsed_secs);
             }
+        } else {
+            eprintln!(
+                "  Skipping failed {} sample for difficulty {}",
+                strategy.label(),
+                difficulty
+            );
         }
+    }
 
-        let elapsed_time = start_time.elapsed(); // Stop timer for this difficulty
-        let elapsed_secs = elapsed_time.as_secs_f64();
-
-        if successful_samples > 0 {
-            let average_nonce_count = total_nonce_count as f64 / successful_samples as f64;
-            let average_elapsed_secs = total_elapsed_secs / successful_samples as f64;
-            let aggregate_hash_rate = if elapsed_secs > 0.0 {
-                total_nonce_count as f64 / elapsed_secs
-            } else {
-                0.0 // Avoid division by zero if time is negligible
-            };
-            println!(
-                "Difficulty: {}, Average Nonce Count: {:.2}, Avg Time: {:.3} s, Aggregate Hash Rate: {:.2} (solutions/s)",
-                difficulty,
-                average_nonce_count,
-                average_elapsed_secs,
-                aggregate_hash_rate
-            );
+    let elapsed_secs = start_time.elapsed().as_secs_f64();
+
+    if successful_samples > 0 {
+        let average_nonce_count = total_nonce_count as f64 / successful_samples as f64;
+        let average_elapsed_secs = total_busy_secs / successful_samples as f64;
+        // Total work divided by the sum of per-thread busy time: the true
+        // aggregate throughput. Dividing by wall-clock instead (as before)
+        // conflates per-core and aggregate rate whenever samples overlap.
+        let true_aggregate_rate = if total_busy_secs > 0.0 {
+            total_nonce_count as f64 / total_busy_secs
         } else {
-            println!("Difficulty: {},NaN,NaN,{:.3},0.0,0", difficulty, elapsed_secs); // Indicate no successful samples
+            0.0
+        };
+        let expected_attempts = difficulty.expected_attempts();
+
+        println!(
+            "Difficulty: {}, Strategy: {}, Average Nonce Count: {:.2} ({:.3}x expected {:.2}), Avg Time: {:.3} s, Wall Time: {:.3} s, True Aggregate Rate: {:.2} (nonces/s)",
+            difficulty,
+            strategy.label(),
+            average_nonce_count,
+            average_nonce_count / expected_attempts,
+            expected_attempts,
+            average_elapsed_secs,
+            elapsed_secs,
+            true_aggregate_rate
+        );
+
+        match RateStats::from_rates(&per_solve_rates) {
+            Some(stats) => println!(
+                "  Per-solve rate (nonces/s): mean {:.2}, median {:.2}, stddev {:.2}, min {:.2}, max {:.2}",
+                stats.mean, stats.median, stats.stddev, stats.min, stats.max
+            ),
+            None => println!("  Per-solve rate: no samples with non-zero elapsed time"),
         }
+    } else {
+        println!(
+            "Difficulty: {}, Strategy: {},NaN,NaN,{:.3},0.0,0",
+            difficulty,
+            strategy.label(),
+            elapsed_secs
+        ); // Indicate no successful samples
     }
-    println!("Measurement complete.");
 }
@@ -0,0 +1,211 @@
+use qpow_math::is_valid_nonce;
+use rand::rngs::StdRng;
+use rand::{thread_rng, RngCore, SeedableRng};
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use crate::difficulty::Difficulty;
+
+/// How many multiples of the expected attempt count a search is allowed to
+/// burn through before assuming something is wrong rather than unlucky.
+const SAFETY_FACTOR: f64 = 100.0;
+
+/// Number of RNG outputs to discard after seeding a worker's RNG, to diffuse
+/// low-quality initial state before it's used for anything that matters.
+const RNG_WARMUP_OUTPUTS: u32 = 10_000;
+
+/// How `find_one_nonce` generates candidate nonces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchStrategy {
+    /// Fill all 64 nonce bytes with fresh random bytes on every attempt.
+    Random,
+    /// Partition the low nonce word across `worker_count` workers that each
+    /// increment linearly through a disjoint slice, mirroring how production
+    /// miners divide the search space. The high bytes hold a random "extra
+    /// nonce" that's re-rolled only when a worker's slice is exhausted.
+    Partitioned { worker_count: u64 },
+}
+
+impl SearchStrategy {
+    pub fn label(self) -> &'static str {
+        match self {
+            SearchStrategy::Random => "random",
+            SearchStrategy::Partitioned { .. } => "partitioned",
+        }
+    }
+}
+
+/// A worker's disjoint slice of the low nonce word, plus the random "extra
+/// nonce" occupying the high bytes.
+struct Partition {
+    start: u64,
+    end: u64,
+    cursor: u64,
+    extra_nonce: [u8; 56],
+    rng: StdRng,
+}
+
+impl Partition {
+    /// The `worker_index`-th of `worker_count` disjoint slices of the low
+    /// nonce word, with a freshly seeded and warmed-up RNG for the extra nonce.
+    fn new(worker_index: u64, worker_count: u64) -> Self {
+        let slice_len = u64::MAX / worker_count;
+        let start = worker_index * slice_len;
+        let end = start.saturating_add(slice_len);
+
+        let mut rng = StdRng::from_entropy();
+        for _ in 0..RNG_WARMUP_OUTPUTS {
+            rng.next_u64();
+        }
+        let mut extra_nonce = [0u8; 56];
+        rng.fill_bytes(&mut extra_nonce);
+
+        Self { start, end, cursor: start, extra_nonce, rng }
+    }
+
+    /// The next candidate nonce in this partition. Re-rolls the extra nonce
+    /// and restarts at the slice's start once the slice is exhausted.
+    fn next_nonce(&mut self) -> [u8; 64] {
+        if self.cursor >= self.end {
+            self.cursor = self.start;
+            self.rng.fill_bytes(&mut self.extra_nonce);
+        }
+        let low = self.cursor;
+        self.cursor += 1;
+
+        let mut nonce_bytes = [0u8; 64];
+        nonce_bytes[..56].copy_from_slice(&self.extra_nonce);
+        nonce_bytes[56..].copy_from_slice(&low.to_be_bytes());
+        nonce_bytes
+    }
+}
+
+/// Searches for one valid nonce under `strategy`, returning
+/// `(total_attempts, elapsed_secs)`, or `(u64::MAX, 0.0)` if the safety limit
+/// was hit before a solution was found.
+pub fn find_one_nonce(
+    strategy: SearchStrategy,
+    difficulty: Difficulty,
+    mining_hash: &[u8; 32],
+) -> (u64, f64) {
+    match strategy {
+        SearchStrategy::Random => find_one_nonce_random(difficulty, mining_hash),
+        SearchStrategy::Partitioned { worker_count } => {
+            find_one_nonce_partitioned(difficulty, mining_hash, worker_count)
+        }
+    }
+}
+
+/// Performs a plain random search for a single valid nonce and returns it,
+/// without the attempt/timing bookkeeping `find_one_nonce` does for
+/// benchmarking. Used to generate known-answer vectors.
+pub fn solve_one(difficulty: Difficulty, mining_hash: &[u8; 32]) -> [u8; 64] {
+    let mut rng = thread_rng();
+    let mut nonce_bytes = [0u8; 64];
+    let safety_limit = (difficulty.expected_attempts() * SAFETY_FACTOR) as u64;
+    let mut attempts: u64 = 0;
+
+    loop {
+        rng.fill_bytes(&mut nonce_bytes);
+        if is_valid_nonce(*mining_hash, nonce_bytes, difficulty.get()) {
+            return nonce_bytes;
+        }
+
+        attempts += 1;
+        if attempts > safety_limit {
+            panic!(
+                "Exceeded safety limit ({} attempts, expected ~{:.1}) solving for difficulty {} while generating vectors",
+                attempts,
+                difficulty.expected_attempts(),
+                difficulty.get()
+            );
+        }
+    }
+}
+
+fn find_one_nonce_random(difficulty: Difficulty, mining_hash: &[u8; 32]) -> (u64, f64) {
+    let mut rng = thread_rng(); // Create RNG inside the function for thread-safety
+    let mut nonce_count: u64 = 0;
+    let mut nonce_bytes = [0u8; 64]; // Buffer for nonce bytes
+    let start_time = Instant::now();
+    let safety_limit = (difficulty.expected_attempts() * SAFETY_FACTOR) as u64;
+
+    loop {
+        nonce_count += 1;
+        rng.fill_bytes(&mut nonce_bytes); // Generate random nonce bytes
+
+        if is_valid_nonce(*mining_hash, nonce_bytes, difficulty.get()) {
+            return (nonce_count, start_time.elapsed().as_secs_f64());
+        }
+
+        if nonce_count > safety_limit || nonce_count == u64::MAX {
+            eprintln!(
+                "Warning: Exceeded safety limit ({} nonces, expected ~{:.1}) for difficulty {}. Skipping.",
+                nonce_count,
+                difficulty.expected_attempts(),
+                difficulty.get()
+            );
+            return (u64::MAX, 0.0);
+        }
+    }
+}
+
+/// Runs `worker_count` partitioned workers concurrently against the same
+/// nonce search; the first to find a valid nonce wins. Mirrors a real miner
+/// partitioning the space across threads instead of every thread racing over
+/// the same random distribution.
+///
+/// Returns `(total_attempts, total_busy_secs)` where `total_busy_secs` is the
+/// sum of each worker's own elapsed time, not the wall-clock of the search —
+/// that keeps it directly comparable to the random strategy's single-thread
+/// `(attempts, elapsed_secs)`, since both then express attempts per unit of
+/// thread-time rather than mixing per-thread and wall-clock figures. Callers
+/// must not also run this call itself inside a parallel sample loop: it
+/// already fans out across `worker_count` threads internally, so nesting it
+/// under another parallel iterator would oversubscribe the thread pool.
+fn find_one_nonce_partitioned(
+    difficulty: Difficulty,
+    mining_hash: &[u8; 32],
+    worker_count: u64,
+) -> (u64, f64) {
+    let found = AtomicBool::new(false);
+    let safety_limit = (difficulty.expected_attempts() * SAFETY_FACTOR / worker_count as f64) as u64;
+
+    let worker_results: Vec<(u64, f64)> = (0..worker_count)
+        .into_par_iter()
+        .map(|worker_index| {
+            let mut partition = Partition::new(worker_index, worker_count);
+            let mut local_attempts: u64 = 0;
+            let worker_start = Instant::now();
+
+            while !found.load(Ordering::Relaxed) {
+                local_attempts += 1;
+                let nonce_bytes = partition.next_nonce();
+
+                if is_valid_nonce(*mining_hash, nonce_bytes, difficulty.get()) {
+                    found.store(true, Ordering::Relaxed);
+                    break;
+                }
+                if local_attempts > safety_limit {
+                    break;
+                }
+            }
+
+            (local_attempts, worker_start.elapsed().as_secs_f64())
+        })
+        .collect();
+
+    if !found.load(Ordering::Relaxed) {
+        eprintln!(
+            "Warning: Exceeded safety limit for difficulty {} across {} partitioned workers. Skipping.",
+            difficulty.get(),
+            worker_count
+        );
+        return (u64::MAX, 0.0);
+    }
+
+    let total_attempts = worker_results.iter().map(|(attempts, _)| attempts).sum();
+    let total_busy_secs = worker_results.iter().map(|(_, secs)| secs).sum();
+    (total_attempts, total_busy_secs)
+}
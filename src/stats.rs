@@ -0,0 +1,40 @@
+/// Summary statistics over a set of per-solve hash rates (nonces/sec).
+#[derive(Debug, Clone, Copy)]
+pub struct RateStats {
+    pub mean: f64,
+    pub median: f64,
+    pub stddev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl RateStats {
+    /// Summarizes `rates`, or `None` if it's empty.
+    pub fn from_rates(rates: &[f64]) -> Option<Self> {
+        if rates.is_empty() {
+            return None;
+        }
+
+        let n = rates.len() as f64;
+        let mean = rates.iter().sum::<f64>() / n;
+        let variance = rates.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n;
+        let stddev = variance.sqrt();
+
+        let mut sorted = rates.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        } else {
+            sorted[mid]
+        };
+
+        Some(Self {
+            mean,
+            median,
+            stddev,
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+        })
+    }
+}
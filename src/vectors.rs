@@ -0,0 +1,168 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// One verification test vector: a mining hash, the nonce that solves it at
+/// `difficulty`, and the difficulty itself. Checked-in vectors let the
+/// verification benchmark (and the regression test built on top of it)
+/// replay known-good inputs instead of re-solving them on every run.
+#[derive(Debug, Clone, Copy)]
+pub struct Vector {
+    pub mining_hash: [u8; 32],
+    pub nonce_bytes: [u8; 64],
+    pub difficulty: u64,
+}
+
+/// Writes `vectors` as one hex-encoded line per vector:
+/// `<mining_hash hex> <nonce hex> <difficulty>`.
+pub fn write_vectors(path: &Path, vectors: &[Vector]) -> io::Result<()> {
+    let mut contents = String::new();
+    for vector in vectors {
+        contents.push_str(&hex::encode(vector.mining_hash));
+        contents.push(' ');
+        contents.push_str(&hex::encode(vector.nonce_bytes));
+        contents.push(' ');
+        contents.push_str(&vector.difficulty.to_string());
+        contents.push('\n');
+    }
+    fs::write(path, contents)
+}
+
+/// Reads vectors previously written by [`write_vectors`].
+///
+/// Returns an `Err` with `ErrorKind::InvalidData` (rather than panicking) if
+/// a line is missing a field, has malformed hex, or has a difficulty that
+/// doesn't parse, so a corrupt vectors file is a recoverable error for the
+/// caller instead of a crash.
+pub fn read_vectors(path: &Path) -> io::Result<Vec<Vector>> {
+    let contents = fs::read_to_string(path)?;
+    let mut vectors = Vec::new();
+
+    for (line_number, line) in contents.lines().enumerate() {
+        let mut fields = line.split_whitespace();
+
+        let mining_hash = parse_hex_array(
+            field(&mut fields, line_number, "mining_hash")?,
+            line_number,
+        )?;
+        let nonce_bytes = parse_hex_array(field(&mut fields, line_number, "nonce")?, line_number)?;
+        let difficulty: u64 = field(&mut fields, line_number, "difficulty")?
+            .parse()
+            .map_err(|_| invalid_data(line_number, "difficulty field is not a valid u64"))?;
+
+        vectors.push(Vector {
+            mining_hash,
+            nonce_bytes,
+            difficulty,
+        });
+    }
+
+    Ok(vectors)
+}
+
+fn field<'a>(
+    fields: &mut std::str::SplitWhitespace<'a>,
+    line_number: usize,
+    name: &str,
+) -> io::Result<&'a str> {
+    fields
+        .next()
+        .ok_or_else(|| invalid_data(line_number, &format!("missing {} field", name)))
+}
+
+fn parse_hex_array<const N: usize>(hex_str: &str, line_number: usize) -> io::Result<[u8; N]> {
+    let bytes = hex::decode(hex_str)
+        .map_err(|_| invalid_data(line_number, "invalid hex"))?;
+    bytes
+        .try_into()
+        .map_err(|_| invalid_data(line_number, &format!("expected {} bytes", N)))
+}
+
+fn invalid_data(line_number: usize, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("vectors file line {}: {}", line_number + 1, message),
+    )
+}
+
+/// Flips the low bit of the last nonce byte. The result is guaranteed to
+/// differ from the original and is vanishingly unlikely to also validate, so
+/// it's useful as a deliberately-invalid perturbation of a known-good nonce.
+pub fn perturb_nonce(nonce_bytes: [u8; 64]) -> [u8; 64] {
+    perturb_nonce_variant(nonce_bytes, 0)
+}
+
+/// Flips the low bit of byte `63 - (attempt % 64)`, cycling through a
+/// different byte on each successive `attempt` so repeated calls don't just
+/// flip the same bit back and forth. Used to try several independent
+/// perturbations of a known-good nonce when one flip isn't guaranteed to
+/// invalidate it (e.g. at a difficulty where the per-nonce validity
+/// probability isn't negligible).
+pub fn perturb_nonce_variant(nonce_bytes: [u8; 64], attempt: usize) -> [u8; 64] {
+    let mut perturbed = nonce_bytes;
+    perturbed[63 - (attempt % 64)] ^= 1;
+    perturbed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_then_read_roundtrips() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("qpow_benchmark_vectors_roundtrip_test.txt");
+        let vectors = vec![
+            Vector {
+                mining_hash: [0xab; 32],
+                nonce_bytes: [0x01; 64],
+                difficulty: 40_000_000_000,
+            },
+            Vector {
+                mining_hash: [0xcd; 32],
+                nonce_bytes: [0xff; 64],
+                difficulty: 58_000_000_000,
+            },
+        ];
+
+        write_vectors(&path, &vectors).expect("write_vectors failed");
+        let read_back = read_vectors(&path).expect("read_vectors failed");
+        fs::remove_file(&path).ok();
+
+        assert_eq!(read_back.len(), vectors.len());
+        for (original, read) in vectors.iter().zip(read_back.iter()) {
+            assert_eq!(original.mining_hash, read.mining_hash);
+            assert_eq!(original.nonce_bytes, read.nonce_bytes);
+            assert_eq!(original.difficulty, read.difficulty);
+        }
+    }
+
+    #[test]
+    fn read_vectors_rejects_corrupt_lines_instead_of_panicking() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("qpow_benchmark_vectors_corrupt_test.txt");
+        fs::write(&path, "not-hex not-hex not-a-number\n").expect("fs::write failed");
+
+        let result = read_vectors(&path);
+        fs::remove_file(&path).ok();
+
+        let err = result.expect_err("corrupt vectors file should be an error, not a panic");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn perturb_nonce_changes_the_nonce() {
+        let nonce_bytes = [0u8; 64];
+        assert_ne!(perturb_nonce(nonce_bytes), nonce_bytes);
+    }
+
+    #[test]
+    fn perturb_nonce_variant_cycles_through_distinct_bytes() {
+        let nonce_bytes = [0u8; 64];
+        let first = perturb_nonce_variant(nonce_bytes, 0);
+        let second = perturb_nonce_variant(nonce_bytes, 1);
+        assert_ne!(first, nonce_bytes);
+        assert_ne!(second, nonce_bytes);
+        assert_ne!(first, second);
+    }
+}